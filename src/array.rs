@@ -0,0 +1,43 @@
+/// Trait for the backing array of a [`RawArrayString`](crate::RawArrayString).
+///
+/// # Safety
+///
+/// Implementors must ensure `as_slice`/`as_mut_slice` return a slice of exactly
+/// `CAPACITY` elements, backed by the array's own storage.
+pub unsafe trait Array: Copy {
+    /// The array's element type.
+    type Item;
+    /// The number of elements the array holds.
+    const CAPACITY: usize;
+    /// Returns the array's elements as a slice.
+    fn as_slice(&self) -> &[Self::Item];
+    /// Returns the array's elements as a mutable slice.
+    fn as_mut_slice(&mut self) -> &mut [Self::Item];
+}
+
+macro_rules! impl_array {
+    ($($len:expr),* $(,)?) => {
+        $(
+            unsafe impl Array for [u8; $len] {
+                type Item = u8;
+                const CAPACITY: usize = $len;
+
+                #[inline]
+                fn as_slice(&self) -> &[u8] {
+                    &self[..]
+                }
+
+                #[inline]
+                fn as_mut_slice(&mut self) -> &mut [u8] {
+                    &mut self[..]
+                }
+            }
+        )*
+    };
+}
+
+impl_array!(
+    0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20, 21, 22, 23, 24, 25,
+    26, 27, 28, 29, 30, 31, 32, 40, 48, 56, 64, 72, 96, 128, 160, 192, 224, 256, 384, 512, 1024,
+    2048, 4096, 8192, 16384, 32768, 65536,
+);
@@ -0,0 +1,29 @@
+use core::fmt;
+
+/// Error value indicating insufficient capacity for an operation, carrying back the
+/// element that didn't fit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CapacityError<T> {
+    element: T,
+}
+
+impl<T> CapacityError<T> {
+    /// Create a new `CapacityError` carrying the element that didn't fit.
+    pub fn new(element: T) -> CapacityError<T> {
+        CapacityError { element }
+    }
+
+    /// Extract the overflowing element.
+    pub fn element(self) -> T {
+        self.element
+    }
+}
+
+impl<T> fmt::Display for CapacityError<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "insufficient capacity")
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T: fmt::Debug> std::error::Error for CapacityError<T> {}
@@ -0,0 +1,12 @@
+//! A fixed-capacity, stack-allocated string type backed by a plain array.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+mod array;
+mod errors;
+mod maybe_uninit;
+mod raw_array_string;
+
+pub use crate::array::Array;
+pub use crate::errors::CapacityError;
+pub use crate::raw_array_string::{CStrError, RawArrayString, Utf16Error};
@@ -0,0 +1,46 @@
+use core::mem::MaybeUninit as StdMaybeUninit;
+
+use crate::array::Array;
+
+/// A `Copy` wrapper around `core::mem::MaybeUninit` for array-backed storage.
+#[derive(Copy, Clone)]
+pub struct MaybeUninit<A: Copy> {
+    inner: StdMaybeUninit<A>,
+}
+
+impl<A> MaybeUninit<A>
+where
+    A: Array<Item = u8> + Copy,
+{
+    /// Create a new uninitialized instance.
+    ///
+    /// # Safety
+    ///
+    /// The contents must be initialized before they are read.
+    pub unsafe fn uninitialized() -> Self {
+        MaybeUninit {
+            inner: StdMaybeUninit::uninit(),
+        }
+    }
+
+    /// Return a pointer to the first byte of the backing array.
+    pub fn ptr(&self) -> *const u8 {
+        self.inner.as_ptr() as *const u8
+    }
+
+    /// Return a mutable pointer to the first byte of the backing array.
+    pub fn ptr_mut(&mut self) -> *mut u8 {
+        self.inner.as_mut_ptr() as *mut u8
+    }
+}
+
+impl<A> From<A> for MaybeUninit<A>
+where
+    A: Array<Item = u8> + Copy,
+{
+    fn from(array: A) -> Self {
+        MaybeUninit {
+            inner: StdMaybeUninit::new(array),
+        }
+    }
+}
@@ -1,352 +1,840 @@
-use std::fmt;
-use std::ops::{Deref, DerefMut};
-use std::ptr;
-use std::slice;
-use std::str;
-use std::str::Utf8Error;
-
-#[cfg(feature = "serde")]
-use serde::{Deserialize, Deserializer, Serialize, Serializer};
-
-use crate::array::Array;
-use crate::errors::CapacityError;
-use crate::maybe_uninit::MaybeUninit as MaybeUninitCopy;
-
-#[derive(Copy)]
-pub struct RawArrayString<A>
-where
-    A: Array<Item = u8> + Copy,
-{
-    xs: MaybeUninitCopy<A>,
-}
-
-impl<A> Default for RawArrayString<A>
-    where A: Array<Item=u8> + Copy
-{
-    /// Return an empty `RawArrayString`
-    fn default() -> RawArrayString<A> {
-        RawArrayString::new()
-    }
-}
-
-
-impl<A> RawArrayString<A>
-where
-    A: Array<Item = u8> + Copy,
-{
-    /// Create a new empty `RawArrayString`.
-    ///
-    /// Capacity is inferred from the type parameter.
-    ///
-    /// ```
-    /// use raw_array_string::RawArrayString;
-    ///
-    /// let mut string = RawArrayString::<[_; 16]>::new();
-    /// string.push_str("foo");
-    /// assert_eq!(&string[..], "foo");
-    /// assert_eq!(string.capacity(), 16);
-    /// ```
-    pub fn new() -> RawArrayString<A> {
-        unsafe {
-            let mut xs = MaybeUninitCopy::uninitialized();
-            *xs.ptr_mut() = 0u8;
-            RawArrayString { xs }
-        }
-    }
-
-    /// Return the length of the string.
-    #[inline]
-    pub fn len(&self) -> usize {
-        let array = self.xs.ptr() as *const A;
-        let s = unsafe { *array };
-        let n = s.as_slice().iter().position(|&x| x == 0u8);
-        match n {
-            Some(n) => n,
-            _ => A::CAPACITY,
-        }
-    }
-
-    /// Returns whether the string is empty.
-    #[inline]
-    pub fn is_empty(&self) -> bool {
-        unsafe { *self.xs.ptr() == 0u8 }
-    }
-
-    /// Create a new `RawArrayString` from a `str`.
-    ///
-    /// Capacity is inferred from the type parameter.
-    ///
-    /// **Errors** if the backing array is not large enough to fit the string.
-    ///
-    /// ```
-    /// use raw_array_string::RawArrayString;
-    ///
-    /// let mut string = RawArrayString::<[_; 3]>::from("foo").unwrap();
-    /// assert_eq!(&string[..], "foo");
-    /// assert_eq!(string.len(), 3);
-    /// assert_eq!(string.capacity(), 3);
-    /// ```
-    pub fn from(s: &str) -> Result<Self, CapacityError<&str>> {
-        let mut arraystr = Self::new();
-        arraystr.try_push_str(s)?;
-        Ok(arraystr)
-    }
-
-    /// Create a new `RawArrayString` from a byte string literal.
-    ///
-    /// **Errors** if the byte string literal is not valid UTF-8.
-    ///
-    /// ```
-    /// use raw_array_string::RawArrayString;
-    ///
-    /// let string = RawArrayString::from_byte_string(b"hello world").unwrap();
-    /// ```
-    pub fn from_byte_string(b: &A) -> Result<Self, Utf8Error> {
-        let len = str::from_utf8(b.as_slice())?.len();
-        debug_assert_eq!(len, A::CAPACITY);
-        Ok(RawArrayString {
-            xs: MaybeUninitCopy::from(*b),
-        })
-    }
-
-    /// Make the string empty.
-    pub fn clear(&mut self) {
-        unsafe {
-            let dst = self.xs.ptr_mut();
-            *dst = 0u8;
-        }
-    }
-
-    /// Return the capacity of the `RawArrayString`.
-    ///
-    /// ```
-    /// use raw_array_string::RawArrayString;
-    ///
-    /// let string = RawArrayString::<[_; 3]>::new();
-    /// assert_eq!(string.capacity(), 3);
-    /// ```
-    #[inline(always)]
-    pub fn capacity(&self) -> usize {
-        A::CAPACITY
-    }
-    /// Return if the `RawArrayString` is completely filled.
-    ///
-    /// ```
-    /// use raw_array_string::RawArrayString;
-    ///
-    /// let mut string = RawArrayString::<[_; 1]>::new();
-    /// assert!(!string.is_full());
-    /// string.push_str("A");
-    /// assert!(string.is_full());
-    /// ```
-    pub fn is_full(&self) -> bool {
-        self.len() == self.capacity()
-    }
-
-    /// Adds the given string slice to the end of the string.
-    ///
-    /// Returns `Ok` if the push succeeds.
-    ///
-    /// **Errors** if the backing array is not large enough to fit the string.
-    ///
-    /// ```
-    /// use raw_array_string::RawArrayString;
-    ///
-    /// let mut string = RawArrayString::<[_; 2]>::new();
-    ///
-    /// string.try_push_str("a").unwrap();
-    /// let overflow1 = string.try_push_str("bc");
-    /// string.try_push_str("d").unwrap();
-    /// let overflow2 = string.try_push_str("ef");
-    ///
-    /// assert_eq!(&string[..], "ad");
-    /// assert_eq!(overflow1.unwrap_err().element(), "bc");
-    /// assert_eq!(overflow2.unwrap_err().element(), "ef");
-    /// ```
-    pub fn try_push_str<'a>(&mut self, s: &'a str) -> Result<(), CapacityError<&'a str>> {
-        if s.len() > self.capacity() - self.len() {
-            return Err(CapacityError::new(s));
-        } else if s.len() == self.capacity() - self.len() {
-            unsafe {
-                let dst = self.xs.ptr_mut().offset(self.len() as isize);
-                let src = s.as_ptr();
-                ptr::copy_nonoverlapping(src, dst, s.len());
-            }
-        } else {
-            unsafe {
-                let dst = self.xs.ptr_mut().offset(self.len() as isize);
-                let src = s.as_ptr();
-                ptr::copy_nonoverlapping(src, dst, s.len());
-                *((dst as usize + s.len()) as *mut u8) = 0u8;
-            }
-        }
-
-        Ok(())
-    }
-
-    /// Adds the given string slice to the end of the string.
-    ///
-    /// ***Panics*** if the backing array is not large enough to fit the string.
-    ///
-    /// ```
-    /// use raw_array_string::RawArrayString;
-    ///
-    /// let mut string = RawArrayString::<[_; 2]>::new();
-    ///
-    /// string.push_str("a");
-    /// string.push_str("d");
-    ///
-    /// assert_eq!(&string[..], "ad");
-    /// ```
-    pub fn push_str(&mut self, s: &str) {
-        self.try_push_str(s).unwrap()
-    }
-}
-
-impl<A> Clone for RawArrayString<A>
-where
-    A: Array<Item = u8> + Copy,
-{
-    fn clone(&self) -> RawArrayString<A> {
-        *self
-    }
-    fn clone_from(&mut self, rhs: &Self) {
-        // guaranteed to fit due to types matching.
-        self.clear();
-        self.try_push_str(rhs).ok();
-    }
-}
-
-impl<A> Deref for RawArrayString<A>
-where
-    A: Array<Item = u8> + Copy,
-{
-    type Target = str;
-    #[inline]
-    fn deref(&self) -> &str {
-        unsafe {
-            let sl = slice::from_raw_parts(self.xs.ptr(), self.len());
-            str::from_utf8_unchecked(sl)
-        }
-    }
-}
-
-impl<A> DerefMut for RawArrayString<A>
-where
-    A: Array<Item = u8> + Copy,
-{
-    #[inline]
-    fn deref_mut(&mut self) -> &mut str {
-        unsafe {
-            let sl = slice::from_raw_parts_mut(self.xs.ptr_mut(), self.len());
-            str::from_utf8_unchecked_mut(sl)
-        }
-    }
-}
-
-impl<A> PartialEq for RawArrayString<A>
-where
-    A: Array<Item = u8> + Copy,
-{
-    fn eq(&self, rhs: &Self) -> bool {
-        **self == **rhs
-    }
-}
-
-impl<A> PartialEq<str> for RawArrayString<A>
-where
-    A: Array<Item = u8> + Copy,
-{
-    fn eq(&self, rhs: &str) -> bool {
-        &**self == rhs
-    }
-}
-
-impl<A> PartialEq<RawArrayString<A>> for str
-where
-    A: Array<Item = u8> + Copy,
-{
-    fn eq(&self, rhs: &RawArrayString<A>) -> bool {
-        self == &**rhs
-    }
-}
-
-impl<A> fmt::Debug for RawArrayString<A>
-where
-    A: Array<Item = u8> + Copy,
-{
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        (**self).fmt(f)
-    }
-}
-
-impl<A> fmt::Display for RawArrayString<A>
-where
-    A: Array<Item = u8> + Copy,
-{
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        (**self).fmt(f)
-    }
-}
-
-#[cfg(feature = "serde")]
-/// Requires crate feature `"serde"`
-impl<A> Serialize for ArrayString<A>
-where
-    A: Array<Item = u8> + Copy,
-{
-    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
-    where
-        S: Serializer,
-    {
-        serializer.serialize_str(&*self)
-    }
-}
-
-#[cfg(feature = "serde")]
-/// Requires crate feature `"serde"`
-impl<'de, A> Deserialize<'de> for ArrayString<A>
-where
-    A: Array<Item = u8> + Copy,
-{
-    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
-    where
-        D: Deserializer<'de>,
-    {
-        use serde::de::{self, Visitor};
-        use std::marker::PhantomData;
-
-        struct ArrayStringVisitor<A: Array>(PhantomData<A>);
-
-        impl<'de, A: Copy + Array<Item = u8>> Visitor<'de> for ArrayStringVisitor<A> {
-            type Value = RawArrayString<A>;
-
-            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
-                write!(
-                    formatter,
-                    "a string no more than {} bytes long",
-                    A::CAPACITY
-                )
-            }
-
-            fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
-            where
-                E: de::Error,
-            {
-                RawArrayString::from(v).map_err(|_| E::invalid_length(v.len(), &self))
-            }
-
-            fn visit_bytes<E>(self, v: &[u8]) -> Result<Self::Value, E>
-            where
-                E: de::Error,
-            {
-                let s = str::from_utf8(v)
-                    .map_err(|_| E::invalid_value(de::Unexpected::Bytes(v), &self))?;
-
-                RawArrayString::from(s).map_err(|_| E::invalid_length(s.len(), &self))
-            }
-        }
-
-        deserializer.deserialize_str(ArrayStringVisitor::<A>(PhantomData))
-    }
-}
+use core::ffi::{c_char, CStr};
+use core::fmt;
+use core::ops::{Deref, DerefMut};
+use core::ptr;
+use core::slice;
+use core::str;
+use core::str::Utf8Error;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::array::Array;
+use crate::errors::CapacityError;
+use crate::maybe_uninit::MaybeUninit as MaybeUninitCopy;
+
+/// `RawArrayString`'s length is tracked separately from its backing array, so it is
+/// sized to the largest capacity we support rather than `usize` on every target.
+type LenUint = u32;
+
+/// Error returned by [`RawArrayString::as_c_str`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CStrError {
+    /// The string's content contains an interior NUL byte.
+    InteriorNul,
+    /// The backing array is completely full, leaving no room for a NUL terminator.
+    NoRoomForNul,
+}
+
+/// Error returned by [`RawArrayString::from_utf16`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Utf16Error {
+    /// The input was not valid UTF-16.
+    InvalidUtf16,
+    /// The decoded string did not fit in the backing array.
+    Capacity,
+}
+
+#[derive(Copy)]
+pub struct RawArrayString<A>
+where
+    A: Array<Item = u8> + Copy,
+{
+    xs: MaybeUninitCopy<A>,
+    len: LenUint,
+}
+
+impl<A> Default for RawArrayString<A>
+    where A: Array<Item=u8> + Copy
+{
+    /// Return an empty `RawArrayString`
+    fn default() -> RawArrayString<A> {
+        RawArrayString::new()
+    }
+}
+
+
+impl<A> RawArrayString<A>
+where
+    A: Array<Item = u8> + Copy,
+{
+    /// Compile-time check that `A::CAPACITY` fits in [`LenUint`], so the `as LenUint`
+    /// casts used to track `len` can't silently truncate. Forced to evaluate at
+    /// monomorphization time by every constructor.
+    const CAPACITY_FITS_LEN_UINT: () = assert!(
+        A::CAPACITY <= LenUint::MAX as usize,
+        "RawArrayString capacity exceeds LenUint::MAX"
+    );
+
+    /// Create a new empty `RawArrayString`.
+    ///
+    /// Capacity is inferred from the type parameter.
+    ///
+    /// ```
+    /// use raw_array_string::RawArrayString;
+    ///
+    /// let mut string = RawArrayString::<[_; 16]>::new();
+    /// string.push_str("foo");
+    /// assert_eq!(&string[..], "foo");
+    /// assert_eq!(string.capacity(), 16);
+    /// ```
+    pub fn new() -> RawArrayString<A> {
+        const { Self::CAPACITY_FITS_LEN_UINT };
+        unsafe {
+            let mut out = RawArrayString {
+                xs: MaybeUninitCopy::uninitialized(),
+                len: 0,
+            };
+            out.write_nul_if_room();
+            out
+        }
+    }
+
+    /// Writes a NUL byte right after the current contents, if there is spare capacity.
+    ///
+    /// This byte is not part of the string's content; it only exists so that
+    /// [`as_c_str`](Self::as_c_str) can hand out a `CStr` view without copying. It is
+    /// kept up to date by every method that can change `len`.
+    #[inline]
+    unsafe fn write_nul_if_room(&mut self) {
+        if self.len() < self.capacity() {
+            *self.xs.ptr_mut().add(self.len()) = 0u8;
+        }
+    }
+
+    /// Return the length of the string.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.len as usize
+    }
+
+    /// Returns whether the string is empty.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Create a new `RawArrayString` from a `str`.
+    ///
+    /// Capacity is inferred from the type parameter.
+    ///
+    /// **Errors** if the backing array is not large enough to fit the string.
+    ///
+    /// ```
+    /// use raw_array_string::RawArrayString;
+    ///
+    /// let mut string = RawArrayString::<[_; 3]>::from("foo").unwrap();
+    /// assert_eq!(&string[..], "foo");
+    /// assert_eq!(string.len(), 3);
+    /// assert_eq!(string.capacity(), 3);
+    /// ```
+    pub fn from(s: &str) -> Result<Self, CapacityError<&str>> {
+        let mut arraystr = Self::new();
+        arraystr.try_push_str(s)?;
+        Ok(arraystr)
+    }
+
+    /// Create a new `RawArrayString` from a byte string literal.
+    ///
+    /// **Errors** if the byte string literal is not valid UTF-8.
+    ///
+    /// ```
+    /// use raw_array_string::RawArrayString;
+    ///
+    /// let string = RawArrayString::from_byte_string(b"hello world").unwrap();
+    /// ```
+    pub fn from_byte_string(b: &A) -> Result<Self, Utf8Error> {
+        const { Self::CAPACITY_FITS_LEN_UINT };
+        str::from_utf8(b.as_slice())?;
+        let mut out = RawArrayString {
+            xs: MaybeUninitCopy::from(*b),
+            len: A::CAPACITY as LenUint,
+        };
+        unsafe { out.write_nul_if_room() };
+        Ok(out)
+    }
+
+    /// Create a new `RawArrayString` from a slice of UTF-16 data.
+    ///
+    /// **Errors** if the slice is not valid UTF-16, or if the decoded string does not
+    /// fit in the backing array.
+    ///
+    /// ```
+    /// use raw_array_string::RawArrayString;
+    ///
+    /// let v: Vec<u16> = "foo".encode_utf16().collect();
+    /// let string = RawArrayString::<[_; 3]>::from_utf16(&v).unwrap();
+    /// assert_eq!(&string[..], "foo");
+    /// ```
+    pub fn from_utf16(v: &[u16]) -> Result<Self, Utf16Error> {
+        let mut out = Self::new();
+        for c in char::decode_utf16(v.iter().copied()) {
+            let ch = c.map_err(|_| Utf16Error::InvalidUtf16)?;
+            out.try_push(ch).map_err(|_| Utf16Error::Capacity)?;
+        }
+        Ok(out)
+    }
+
+    /// Create a new `RawArrayString` from a slice of UTF-16 data, replacing any
+    /// invalid UTF-16 with `U+FFFD REPLACEMENT CHARACTER`.
+    ///
+    /// **Errors** if the decoded string does not fit in the backing array.
+    ///
+    /// ```
+    /// use raw_array_string::RawArrayString;
+    ///
+    /// let v: Vec<u16> = "foo".encode_utf16().collect();
+    /// let string = RawArrayString::<[_; 3]>::from_utf16_lossy(&v).unwrap();
+    /// assert_eq!(&string[..], "foo");
+    /// ```
+    pub fn from_utf16_lossy(v: &[u16]) -> Result<Self, CapacityError<char>> {
+        let mut out = Self::new();
+        for c in char::decode_utf16(v.iter().copied()) {
+            let ch = c.unwrap_or(char::REPLACEMENT_CHARACTER);
+            out.try_push(ch)?;
+        }
+        Ok(out)
+    }
+
+    /// Make the string empty.
+    pub fn clear(&mut self) {
+        self.len = 0;
+        unsafe { self.write_nul_if_room() };
+    }
+
+    /// Return the capacity of the `RawArrayString`.
+    ///
+    /// ```
+    /// use raw_array_string::RawArrayString;
+    ///
+    /// let string = RawArrayString::<[_; 3]>::new();
+    /// assert_eq!(string.capacity(), 3);
+    /// ```
+    #[inline(always)]
+    pub fn capacity(&self) -> usize {
+        A::CAPACITY
+    }
+    /// Return if the `RawArrayString` is completely filled.
+    ///
+    /// ```
+    /// use raw_array_string::RawArrayString;
+    ///
+    /// let mut string = RawArrayString::<[_; 1]>::new();
+    /// assert!(!string.is_full());
+    /// string.push_str("A");
+    /// assert!(string.is_full());
+    /// ```
+    pub fn is_full(&self) -> bool {
+        self.len() == self.capacity()
+    }
+
+    /// Adds the given string slice to the end of the string.
+    ///
+    /// Returns `Ok` if the push succeeds.
+    ///
+    /// **Errors** if the backing array is not large enough to fit the string.
+    ///
+    /// ```
+    /// use raw_array_string::RawArrayString;
+    ///
+    /// let mut string = RawArrayString::<[_; 2]>::new();
+    ///
+    /// string.try_push_str("a").unwrap();
+    /// let overflow1 = string.try_push_str("bc");
+    /// string.try_push_str("d").unwrap();
+    /// let overflow2 = string.try_push_str("ef");
+    ///
+    /// assert_eq!(&string[..], "ad");
+    /// assert_eq!(overflow1.unwrap_err().element(), "bc");
+    /// assert_eq!(overflow2.unwrap_err().element(), "ef");
+    /// ```
+    pub fn try_push_str<'a>(&mut self, s: &'a str) -> Result<(), CapacityError<&'a str>> {
+        if s.len() > self.capacity() - self.len() {
+            return Err(CapacityError::new(s));
+        }
+
+        unsafe {
+            let dst = self.xs.ptr_mut().add(self.len());
+            let src = s.as_ptr();
+            ptr::copy_nonoverlapping(src, dst, s.len());
+            self.len += s.len() as LenUint;
+            self.write_nul_if_room();
+        }
+
+        Ok(())
+    }
+
+    /// Adds the given string slice to the end of the string.
+    ///
+    /// ***Panics*** if the backing array is not large enough to fit the string.
+    ///
+    /// ```
+    /// use raw_array_string::RawArrayString;
+    ///
+    /// let mut string = RawArrayString::<[_; 2]>::new();
+    ///
+    /// string.push_str("a");
+    /// string.push_str("d");
+    ///
+    /// assert_eq!(&string[..], "ad");
+    /// ```
+    pub fn push_str(&mut self, s: &str) {
+        self.try_push_str(s).unwrap()
+    }
+
+    /// Adds the given char to the end of the string.
+    ///
+    /// Returns `Ok` if the push succeeds.
+    ///
+    /// **Errors** if the backing array is not large enough to fit the char.
+    ///
+    /// ```
+    /// use raw_array_string::RawArrayString;
+    ///
+    /// let mut string = RawArrayString::<[_; 2]>::new();
+    ///
+    /// string.try_push('a').unwrap();
+    /// let overflow = string.try_push('€');
+    ///
+    /// assert_eq!(&string[..], "a");
+    /// assert!(overflow.is_err());
+    /// ```
+    pub fn try_push(&mut self, ch: char) -> Result<(), CapacityError<char>> {
+        let len_utf8 = ch.len_utf8();
+        if len_utf8 > self.capacity() - self.len() {
+            return Err(CapacityError::new(ch));
+        }
+
+        unsafe {
+            let mut buf = [0u8; 4];
+            let encoded = ch.encode_utf8(&mut buf).as_bytes();
+            let dst = self.xs.ptr_mut().add(self.len());
+            ptr::copy_nonoverlapping(encoded.as_ptr(), dst, len_utf8);
+            self.len += len_utf8 as LenUint;
+            self.write_nul_if_room();
+        }
+
+        Ok(())
+    }
+
+    /// Adds the given char to the end of the string.
+    ///
+    /// ***Panics*** if the backing array is not large enough to fit the char.
+    ///
+    /// ```
+    /// use raw_array_string::RawArrayString;
+    ///
+    /// let mut string = RawArrayString::<[_; 2]>::new();
+    ///
+    /// string.push('a');
+    /// string.push('d');
+    ///
+    /// assert_eq!(&string[..], "ad");
+    /// ```
+    pub fn push(&mut self, ch: char) {
+        self.try_push(ch).unwrap()
+    }
+
+    /// Removes the last char from the string and returns it.
+    ///
+    /// Returns `None` if the string is empty.
+    ///
+    /// ```
+    /// use raw_array_string::RawArrayString;
+    ///
+    /// let mut string = RawArrayString::<[_; 2]>::from("ad").unwrap();
+    ///
+    /// assert_eq!(string.pop(), Some('d'));
+    /// assert_eq!(string.pop(), Some('a'));
+    /// assert_eq!(string.pop(), None);
+    /// ```
+    pub fn pop(&mut self) -> Option<char> {
+        let ch = self.chars().next_back()?;
+        self.len -= ch.len_utf8() as LenUint;
+        unsafe { self.write_nul_if_room() };
+        Some(ch)
+    }
+
+    /// Inserts the given char at the given byte index of the string.
+    ///
+    /// Returns `Ok` if the insertion succeeds.
+    ///
+    /// **Errors** if the backing array is not large enough to fit the char.
+    ///
+    /// ***Panics*** if `idx` does not lie on a `char` boundary.
+    ///
+    /// ```
+    /// use raw_array_string::RawArrayString;
+    ///
+    /// let mut string = RawArrayString::<[_; 3]>::from("ad").unwrap();
+    /// string.try_insert(1, 'b').unwrap();
+    ///
+    /// assert_eq!(&string[..], "abd");
+    /// ```
+    pub fn try_insert(&mut self, idx: usize, ch: char) -> Result<(), CapacityError<char>> {
+        assert!(self.is_char_boundary(idx));
+        let len_utf8 = ch.len_utf8();
+        if len_utf8 > self.capacity() - self.len() {
+            return Err(CapacityError::new(ch));
+        }
+
+        unsafe {
+            let mut buf = [0u8; 4];
+            let encoded = ch.encode_utf8(&mut buf).as_bytes();
+            let base = self.xs.ptr_mut();
+            let src = base.add(idx);
+            ptr::copy(src, src.add(len_utf8), self.len() - idx);
+            ptr::copy_nonoverlapping(encoded.as_ptr(), src, len_utf8);
+            self.len += len_utf8 as LenUint;
+            self.write_nul_if_room();
+        }
+
+        Ok(())
+    }
+
+    /// Inserts the given char at the given byte index of the string.
+    ///
+    /// ***Panics*** if the backing array is not large enough to fit the char, or if `idx`
+    /// does not lie on a `char` boundary.
+    ///
+    /// ```
+    /// use raw_array_string::RawArrayString;
+    ///
+    /// let mut string = RawArrayString::<[_; 3]>::from("ad").unwrap();
+    /// string.insert(1, 'b');
+    ///
+    /// assert_eq!(&string[..], "abd");
+    /// ```
+    pub fn insert(&mut self, idx: usize, ch: char) {
+        self.try_insert(idx, ch).unwrap()
+    }
+
+    /// Removes the char at the given byte index of the string and returns it.
+    ///
+    /// ***Panics*** if `idx` does not lie on a `char` boundary or is out of bounds.
+    ///
+    /// ```
+    /// use raw_array_string::RawArrayString;
+    ///
+    /// let mut string = RawArrayString::<[_; 3]>::from("abd").unwrap();
+    ///
+    /// assert_eq!(string.remove(1), 'b');
+    /// assert_eq!(&string[..], "ad");
+    /// ```
+    pub fn remove(&mut self, idx: usize) -> char {
+        let ch = match self[idx..].chars().next() {
+            Some(ch) => ch,
+            None => panic!("cannot remove a char from the end of a string"),
+        };
+        let next = idx + ch.len_utf8();
+
+        unsafe {
+            let base = self.xs.ptr_mut();
+            ptr::copy(base.add(next), base.add(idx), self.len() - next);
+            self.len -= ch.len_utf8() as LenUint;
+            self.write_nul_if_room();
+        }
+
+        ch
+    }
+
+    /// Returns the string as a NUL-terminated `CStr`, without copying.
+    ///
+    /// This is cheap because the backing array always has a NUL byte right after the
+    /// content whenever there is spare capacity for one.
+    ///
+    /// **Errors** if the content contains an interior NUL byte, or if the backing
+    /// array is completely full and has no room for a terminator.
+    ///
+    /// ```
+    /// use raw_array_string::RawArrayString;
+    ///
+    /// let string = RawArrayString::<[_; 4]>::from("foo").unwrap();
+    /// assert_eq!(string.as_c_str().unwrap().to_str(), Ok("foo"));
+    /// ```
+    pub fn as_c_str(&self) -> Result<&CStr, CStrError> {
+        if self.len() == self.capacity() {
+            return Err(CStrError::NoRoomForNul);
+        }
+        if self.as_bytes().contains(&0u8) {
+            return Err(CStrError::InteriorNul);
+        }
+        unsafe {
+            let sl = slice::from_raw_parts(self.xs.ptr(), self.len() + 1);
+            Ok(CStr::from_bytes_with_nul_unchecked(sl))
+        }
+    }
+
+    /// Returns a raw pointer to the string's content, suitable for passing to C APIs
+    /// that expect a `const char*`.
+    ///
+    /// Whether the pointed-to data is NUL-terminated depends on [`as_c_str`]; callers
+    /// that need a guaranteed C string should call that instead.
+    ///
+    /// [`as_c_str`]: Self::as_c_str
+    #[inline]
+    pub fn as_ptr(&self) -> *const c_char {
+        self.xs.ptr() as *const c_char
+    }
+
+    /// Shortens the string to `new_len` bytes.
+    ///
+    /// Does nothing if `new_len` is greater than the string's current length.
+    ///
+    /// ***Panics*** if `new_len` does not lie on a `char` boundary.
+    ///
+    /// ```
+    /// use raw_array_string::RawArrayString;
+    ///
+    /// let mut string = RawArrayString::<[_; 3]>::from("abd").unwrap();
+    /// string.truncate(1);
+    ///
+    /// assert_eq!(&string[..], "a");
+    /// ```
+    pub fn truncate(&mut self, new_len: usize) {
+        if new_len >= self.len() {
+            return;
+        }
+        assert!(self.is_char_boundary(new_len));
+        unsafe {
+            self.set_len(new_len);
+        }
+    }
+
+    /// Forcibly sets the length of the string to `new_len`.
+    ///
+    /// This does not read or move any of the string's bytes; it updates the length
+    /// field and, if there is spare capacity, writes the NUL byte that follows the
+    /// contents. It is up to the caller to have already written valid UTF-8 up to
+    /// `new_len` into the backing array.
+    ///
+    /// # Safety
+    ///
+    /// - `new_len` must be less than or equal to [`capacity`](Self::capacity).
+    /// - the bytes at `[0, new_len)` must be valid UTF-8.
+    #[inline]
+    pub unsafe fn set_len(&mut self, new_len: usize) {
+        debug_assert!(new_len <= self.capacity());
+        self.len = new_len as LenUint;
+        self.write_nul_if_room();
+    }
+
+    /// Retains only the chars for which the predicate returns `true`, removing the
+    /// rest in place, without reallocation.
+    ///
+    /// ```
+    /// use raw_array_string::RawArrayString;
+    ///
+    /// let mut string = RawArrayString::<[_; 5]>::from("a1b2c").unwrap();
+    /// string.retain(|ch| ch.is_alphabetic());
+    ///
+    /// assert_eq!(&string[..], "abc");
+    /// ```
+    pub fn retain<F: FnMut(char) -> bool>(&mut self, mut f: F) {
+        // If `f` panics partway through, `self.len` must never be left covering bytes
+        // we've already shifted out of place. This guard keeps `len` truncated to the
+        // last known-consistent prefix on every exit, including unwind, mirroring
+        // `std::String::retain`'s `SetLenOnDrop`.
+        struct SetLenOnDrop<'a, A>
+        where
+            A: Array<Item = u8> + Copy,
+        {
+            s: &'a mut RawArrayString<A>,
+            idx: usize,
+            del_bytes: usize,
+        }
+
+        impl<A> Drop for SetLenOnDrop<'_, A>
+        where
+            A: Array<Item = u8> + Copy,
+        {
+            fn drop(&mut self) {
+                let new_len = self.idx - self.del_bytes;
+                debug_assert!(new_len <= self.s.len());
+                unsafe { self.s.set_len(new_len) };
+            }
+        }
+
+        let len = self.len();
+        let mut guard = SetLenOnDrop {
+            s: self,
+            idx: 0,
+            del_bytes: 0,
+        };
+
+        while guard.idx < len {
+            // Borrowed only long enough to decode the current char; no live reference
+            // is held across the `ptr::copy` write below.
+            let ch = unsafe { guard.s.get_unchecked(guard.idx..len) }
+                .chars()
+                .next()
+                .unwrap();
+            let len_utf8 = ch.len_utf8();
+
+            // `idx` only advances past a char once it has been fully accounted for
+            // (either marked deleted or copied into place), so a panic from `f`
+            // never leaves the guard's invariant, `idx - del_bytes` is a valid
+            // already-compacted length, in a state where it isn't.
+            if !f(ch) {
+                guard.del_bytes += len_utf8;
+                guard.idx += len_utf8;
+                continue;
+            }
+
+            if guard.del_bytes > 0 {
+                unsafe {
+                    let base = guard.s.xs.ptr_mut();
+                    ptr::copy(
+                        base.add(guard.idx),
+                        base.add(guard.idx - guard.del_bytes),
+                        len_utf8,
+                    );
+                }
+            }
+            guard.idx += len_utf8;
+        }
+    }
+}
+
+impl<A> Clone for RawArrayString<A>
+where
+    A: Array<Item = u8> + Copy,
+{
+    fn clone(&self) -> RawArrayString<A> {
+        *self
+    }
+}
+
+impl<A> Deref for RawArrayString<A>
+where
+    A: Array<Item = u8> + Copy,
+{
+    type Target = str;
+    #[inline]
+    fn deref(&self) -> &str {
+        unsafe {
+            let sl = slice::from_raw_parts(self.xs.ptr(), self.len());
+            str::from_utf8_unchecked(sl)
+        }
+    }
+}
+
+impl<A> DerefMut for RawArrayString<A>
+where
+    A: Array<Item = u8> + Copy,
+{
+    #[inline]
+    fn deref_mut(&mut self) -> &mut str {
+        unsafe {
+            let sl = slice::from_raw_parts_mut(self.xs.ptr_mut(), self.len());
+            str::from_utf8_unchecked_mut(sl)
+        }
+    }
+}
+
+impl<A> PartialEq for RawArrayString<A>
+where
+    A: Array<Item = u8> + Copy,
+{
+    fn eq(&self, rhs: &Self) -> bool {
+        **self == **rhs
+    }
+}
+
+impl<A> PartialEq<str> for RawArrayString<A>
+where
+    A: Array<Item = u8> + Copy,
+{
+    fn eq(&self, rhs: &str) -> bool {
+        &**self == rhs
+    }
+}
+
+impl<A> PartialEq<RawArrayString<A>> for str
+where
+    A: Array<Item = u8> + Copy,
+{
+    fn eq(&self, rhs: &RawArrayString<A>) -> bool {
+        self == &**rhs
+    }
+}
+
+impl<A> Eq for RawArrayString<A> where A: Array<Item = u8> + Copy {}
+
+impl<A> core::hash::Hash for RawArrayString<A>
+where
+    A: Array<Item = u8> + Copy,
+{
+    fn hash<H: core::hash::Hasher>(&self, h: &mut H) {
+        (**self).hash(h)
+    }
+}
+
+impl<A> PartialOrd for RawArrayString<A>
+where
+    A: Array<Item = u8> + Copy,
+{
+    fn partial_cmp(&self, rhs: &Self) -> Option<core::cmp::Ordering> {
+        Some(self.cmp(rhs))
+    }
+}
+
+impl<A> Ord for RawArrayString<A>
+where
+    A: Array<Item = u8> + Copy,
+{
+    fn cmp(&self, rhs: &Self) -> core::cmp::Ordering {
+        (**self).cmp(&**rhs)
+    }
+}
+
+impl<A> core::borrow::Borrow<str> for RawArrayString<A>
+where
+    A: Array<Item = u8> + Copy,
+{
+    fn borrow(&self) -> &str {
+        self
+    }
+}
+
+impl<A> AsRef<str> for RawArrayString<A>
+where
+    A: Array<Item = u8> + Copy,
+{
+    fn as_ref(&self) -> &str {
+        self
+    }
+}
+
+impl<A> AsRef<[u8]> for RawArrayString<A>
+where
+    A: Array<Item = u8> + Copy,
+{
+    fn as_ref(&self) -> &[u8] {
+        self.as_bytes()
+    }
+}
+
+impl<A> str::FromStr for RawArrayString<A>
+where
+    A: Array<Item = u8> + Copy,
+{
+    type Err = CapacityError<()>;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        RawArrayString::from(s).map_err(|_| CapacityError::new(()))
+    }
+}
+
+impl<'a, A> core::convert::TryFrom<&'a str> for RawArrayString<A>
+where
+    A: Array<Item = u8> + Copy,
+{
+    type Error = CapacityError<&'a str>;
+
+    fn try_from(s: &'a str) -> Result<Self, Self::Error> {
+        RawArrayString::from(s)
+    }
+}
+
+impl<A, I> core::ops::Index<I> for RawArrayString<A>
+where
+    A: Array<Item = u8> + Copy,
+    I: core::slice::SliceIndex<str>,
+{
+    type Output = I::Output;
+
+    fn index(&self, index: I) -> &I::Output {
+        core::ops::Index::index(&**self, index)
+    }
+}
+
+impl<A, I> core::ops::IndexMut<I> for RawArrayString<A>
+where
+    A: Array<Item = u8> + Copy,
+    I: core::slice::SliceIndex<str>,
+{
+    fn index_mut(&mut self, index: I) -> &mut I::Output {
+        core::ops::IndexMut::index_mut(&mut **self, index)
+    }
+}
+
+impl<A> fmt::Debug for RawArrayString<A>
+where
+    A: Array<Item = u8> + Copy,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        (**self).fmt(f)
+    }
+}
+
+impl<A> fmt::Display for RawArrayString<A>
+where
+    A: Array<Item = u8> + Copy,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        (**self).fmt(f)
+    }
+}
+
+#[cfg(feature = "serde")]
+/// Requires crate feature `"serde"`
+impl<A> Serialize for RawArrayString<A>
+where
+    A: Array<Item = u8> + Copy,
+{
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(self)
+    }
+}
+
+#[cfg(feature = "serde")]
+/// Requires crate feature `"serde"`
+impl<'de, A> Deserialize<'de> for RawArrayString<A>
+where
+    A: Array<Item = u8> + Copy,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        use serde::de::{self, Visitor};
+        use core::marker::PhantomData;
+
+        struct ArrayStringVisitor<A: Array>(PhantomData<A>);
+
+        impl<'de, A: Copy + Array<Item = u8>> Visitor<'de> for ArrayStringVisitor<A> {
+            type Value = RawArrayString<A>;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                write!(
+                    formatter,
+                    "a string no more than {} bytes long",
+                    A::CAPACITY
+                )
+            }
+
+            fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+            where
+                E: de::Error,
+            {
+                RawArrayString::from(v).map_err(|_| E::invalid_length(v.len(), &self))
+            }
+
+            fn visit_bytes<E>(self, v: &[u8]) -> Result<Self::Value, E>
+            where
+                E: de::Error,
+            {
+                let s = str::from_utf8(v)
+                    .map_err(|_| E::invalid_value(de::Unexpected::Bytes(v), &self))?;
+
+                RawArrayString::from(s).map_err(|_| E::invalid_length(s.len(), &self))
+            }
+        }
+
+        deserializer.deserialize_str(ArrayStringVisitor::<A>(PhantomData))
+    }
+}
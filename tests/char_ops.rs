@@ -0,0 +1,38 @@
+extern crate raw_array_string;
+
+use raw_array_string::RawArrayString;
+
+#[test]
+#[should_panic(expected = "is_char_boundary")]
+fn insert_panics_on_non_char_boundary() {
+    let mut string = RawArrayString::<[u8; 8]>::from("a\u{1F600}b").unwrap();
+    // Byte 2 is in the middle of the 4-byte emoji, not a char boundary.
+    string.insert(2, 'x');
+}
+
+#[test]
+fn try_insert_errors_without_room() {
+    let mut string = RawArrayString::<[u8; 2]>::from("ab").unwrap();
+    assert_eq!(string.try_insert(1, 'c').unwrap_err().element(), 'c');
+    assert_eq!(&string[..], "ab");
+}
+
+#[test]
+#[should_panic]
+fn remove_panics_on_non_char_boundary() {
+    let mut string = RawArrayString::<[u8; 8]>::from("a\u{1F600}b").unwrap();
+    string.remove(2);
+}
+
+#[test]
+#[should_panic(expected = "cannot remove a char from the end of a string")]
+fn remove_panics_out_of_bounds() {
+    let mut string = RawArrayString::<[u8; 4]>::from("ab").unwrap();
+    string.remove(2);
+}
+
+#[test]
+fn pop_on_empty_returns_none() {
+    let mut string = RawArrayString::<[u8; 4]>::new();
+    assert_eq!(string.pop(), None);
+}
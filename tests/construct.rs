@@ -0,0 +1,26 @@
+extern crate raw_array_string;
+
+use raw_array_string::RawArrayString;
+
+#[test]
+fn from_errors_without_room() {
+    assert_eq!(
+        RawArrayString::<[u8; 2]>::from("abc").unwrap_err().element(),
+        "abc"
+    );
+}
+
+#[test]
+fn from_byte_string_errors_on_invalid_utf8() {
+    let invalid: [u8; 2] = [0xFF, 0xFF];
+    assert!(RawArrayString::from_byte_string(&invalid).is_err());
+}
+
+#[test]
+fn clear_resets_len_to_zero() {
+    let mut string = RawArrayString::<[u8; 4]>::from("abcd").unwrap();
+    string.clear();
+    assert_eq!(string.len(), 0);
+    assert!(string.is_empty());
+    assert_eq!(&string[..], "");
+}
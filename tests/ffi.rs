@@ -0,0 +1,22 @@
+extern crate raw_array_string;
+
+use raw_array_string::{CStrError, RawArrayString};
+
+#[test]
+fn as_c_str_errors_on_interior_nul() {
+    let string = RawArrayString::<[u8; 4]>::from("a\0b").unwrap();
+    assert_eq!(string.as_c_str().unwrap_err(), CStrError::InteriorNul);
+}
+
+#[test]
+fn as_c_str_errors_when_backing_array_is_full() {
+    let string = RawArrayString::<[u8; 3]>::from("abc").unwrap();
+    assert!(string.is_full());
+    assert_eq!(string.as_c_str().unwrap_err(), CStrError::NoRoomForNul);
+}
+
+#[test]
+fn as_c_str_succeeds_with_spare_capacity() {
+    let string = RawArrayString::<[u8; 4]>::from("abc").unwrap();
+    assert_eq!(string.as_c_str().unwrap().to_str(), Ok("abc"));
+}
@@ -0,0 +1,49 @@
+extern crate raw_array_string;
+
+use raw_array_string::RawArrayString;
+
+#[test]
+fn retain_removes_everything() {
+    let mut string = RawArrayString::<[u8; 5]>::from("abcde").unwrap();
+    string.retain(|_| false);
+    assert_eq!(&string[..], "");
+    assert_eq!(string.len(), 0);
+}
+
+#[test]
+fn retain_keeps_everything() {
+    let mut string = RawArrayString::<[u8; 5]>::from("abcde").unwrap();
+    string.retain(|_| true);
+    assert_eq!(&string[..], "abcde");
+    assert_eq!(string.len(), 5);
+}
+
+#[test]
+fn retain_handles_multibyte_chars() {
+    let mut string = RawArrayString::<[u8; 20]>::from("a\u{1F600}b\u{1F600}c").unwrap();
+    string.retain(|ch| ch != '\u{1F600}');
+    assert_eq!(&string[..], "abc");
+}
+
+#[test]
+fn retain_leaves_string_valid_utf8_if_predicate_panics() {
+    let mut string = RawArrayString::<[u8; 20]>::from("a\u{1F600}XY").unwrap();
+    let mut count = 0;
+
+    let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+        string.retain(|_ch| {
+            count += 1;
+            match count {
+                1 => false,
+                2 => panic!("boom"),
+                _ => true,
+            }
+        });
+    }));
+
+    assert!(result.is_err());
+    // Even though the predicate panicked mid-retain, the string must still be a
+    // valid, readable RawArrayString: `len` can't cover bytes that were shifted
+    // out of place by the interrupted pass.
+    assert!(std::str::from_utf8(string.as_bytes()).is_ok());
+}
@@ -0,0 +1,51 @@
+extern crate raw_array_string;
+
+use std::collections::{BTreeMap, HashMap};
+use std::convert::TryFrom;
+use std::str::FromStr;
+
+use raw_array_string::RawArrayString;
+
+#[test]
+fn works_as_a_hashmap_key() {
+    let mut map = HashMap::new();
+    map.insert(RawArrayString::<[u8; 8]>::from("key").unwrap(), 1);
+    assert_eq!(map.get("key"), Some(&1));
+}
+
+#[test]
+fn works_as_a_btreemap_key() {
+    let mut map = BTreeMap::new();
+    map.insert(RawArrayString::<[u8; 8]>::from("b").unwrap(), 2);
+    map.insert(RawArrayString::<[u8; 8]>::from("a").unwrap(), 1);
+
+    let keys: Vec<&str> = map.keys().map(|k| &k[..]).collect();
+    assert_eq!(keys, vec!["a", "b"]);
+}
+
+#[test]
+fn from_str_round_trips() {
+    let string = RawArrayString::<[u8; 8]>::from_str("hello").unwrap();
+    assert_eq!(&string[..], "hello");
+}
+
+#[test]
+fn from_str_errors_without_room() {
+    assert!(RawArrayString::<[u8; 2]>::from_str("hello").is_err());
+}
+
+#[test]
+fn try_from_round_trips() {
+    let string = RawArrayString::<[u8; 8]>::try_from("hello").unwrap();
+    assert_eq!(&string[..], "hello");
+}
+
+#[test]
+fn try_from_errors_without_room() {
+    assert_eq!(
+        RawArrayString::<[u8; 2]>::try_from("hello")
+            .unwrap_err()
+            .element(),
+        "hello"
+    );
+}
@@ -0,0 +1,35 @@
+extern crate raw_array_string;
+
+use raw_array_string::{RawArrayString, Utf16Error};
+
+#[test]
+fn from_utf16_rejects_unpaired_surrogate() {
+    // 0xD800 is a lone high surrogate with nothing following it.
+    let v = [0xD800u16];
+    assert_eq!(
+        RawArrayString::<[u8; 8]>::from_utf16(&v).unwrap_err(),
+        Utf16Error::InvalidUtf16
+    );
+}
+
+#[test]
+fn from_utf16_rejects_overflow() {
+    let v: Vec<u16> = "hello".encode_utf16().collect();
+    assert_eq!(
+        RawArrayString::<[u8; 2]>::from_utf16(&v).unwrap_err(),
+        Utf16Error::Capacity
+    );
+}
+
+#[test]
+fn from_utf16_lossy_replaces_unpaired_surrogate() {
+    let v = [0xD800u16];
+    let string = RawArrayString::<[u8; 4]>::from_utf16_lossy(&v).unwrap();
+    assert_eq!(&string[..], "\u{FFFD}");
+}
+
+#[test]
+fn from_utf16_lossy_errors_without_room() {
+    let v = [0xD800u16];
+    assert!(RawArrayString::<[u8; 2]>::from_utf16_lossy(&v).is_err());
+}